@@ -1,8 +1,25 @@
-use regex::Regex;
+use ignore::{WalkBuilder, WalkState};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
 use std::env;
-use std::io;
+use std::io::{self, IsTerminal};
+use std::path::Path;
 use std::process;
-use walkdir::WalkDir;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, PartialEq)]
+enum EntryType {
+    File,
+    Dir,
+    Symlink,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
 
 struct Options {
     base_name: bool,
@@ -10,6 +27,13 @@ struct Options {
     count: bool,
     limit: u32,
     regex: Option<Regex>,
+    exec: Option<Vec<String>>,
+    hidden: bool,
+    no_ignore: bool,
+    max_depth: Option<usize>,
+    entry_type: Option<EntryType>,
+    extensions: Vec<String>,
+    color: ColorMode,
 }
 
 impl Options {
@@ -20,8 +44,91 @@ impl Options {
             count: false,
             limit: u32::MAX,
             regex: None,
+            exec: None,
+            hidden: false,
+            no_ignore: false,
+            max_depth: None,
+            entry_type: None,
+            extensions: Vec::new(),
+            color: ColorMode::Auto,
+        }
+    }
+}
+
+// A parsed LS_COLORS table, falling back to sane defaults when the variable is unset.
+struct LsColors {
+    extensions: HashMap<String, String>,
+    dir: Option<String>,
+    symlink: Option<String>,
+    exec: Option<String>,
+}
+
+impl LsColors {
+    fn from_env() -> Self {
+        let mut colors = LsColors {
+            extensions: HashMap::new(),
+            dir: Some("01;34".to_string()),
+            symlink: Some("01;36".to_string()),
+            exec: Some("01;32".to_string()),
+        };
+
+        if let Ok(spec) = env::var("LS_COLORS") {
+            for entry in spec.split(':') {
+                let Some((key, value)) = entry.split_once('=') else {
+                    continue;
+                };
+
+                if value.is_empty() {
+                    continue;
+                }
+
+                match key {
+                    "di" => colors.dir = Some(value.to_string()),
+                    "ln" => colors.symlink = Some(value.to_string()),
+                    "ex" => colors.exec = Some(value.to_string()),
+                    _ if key.starts_with("*.") => {
+                        colors
+                            .extensions
+                            .insert(key[2..].to_lowercase(), value.to_string());
+                    }
+                    _ => {}
+                }
+            }
         }
+
+        colors
     }
+
+    // Returns the ANSI SGR code that should be applied to `path`, if any.
+    fn color_for(&self, path: &Path) -> Option<String> {
+        let metadata = std::fs::symlink_metadata(path).ok()?;
+        let file_type = metadata.file_type();
+
+        if file_type.is_symlink() {
+            return self.symlink.clone();
+        }
+        if file_type.is_dir() {
+            return self.dir.clone();
+        }
+
+        if is_executable(&metadata) && self.exec.is_some() {
+            return self.exec.clone();
+        }
+
+        let ext = path.extension().and_then(|e| e.to_str())?.to_lowercase();
+        self.extensions.get(&ext).cloned()
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &std::fs::Metadata) -> bool {
+    false
 }
 
 fn main() -> io::Result<()> {
@@ -33,107 +140,398 @@ fn main() -> io::Result<()> {
         .to_string_lossy()
         .into_owned();
 
-    check_args(&args, &file_name, &mut options);
-
-    let search_term = &args[args.len() - 1];
-    find_file(search_term, &options);
+    let search_term = parse_args(&args, &file_name, &mut options);
+    find_file(&search_term, &options);
 
     Ok(())
 }
 
-fn check_args(args: &[String], file_name: &str, options: &mut Options) {
-    if args.len() == 1 || ((args[1] == "/l" || args[1] == "--limit") && args.len() > 4) {
+fn print_help(file_name: &str) {
+    println!("Usage: {}[.exe] [args] <search_term>", file_name);
+    println!("\nOPTIONAL ARGUMENTS\n");
+    println!("/h or --help              Displays this help message");
+    println!("/b or --basename          Searches for files using their basename instead of their full path (case-insenitive)");
+    println!("/s or --case-sensitive    Searches for files using case-sensitive search");
+    println!("/c or --count             Only displays the number of matches and not the files matched");
+    println!("/l or --limit <number>    Limits the number of results displayed");
+    println!("/r or --regex <regexp>    Searches for files based on a regular expression");
+    println!("/x or --exec <command>    Runs <command> for every matched path, substituting {{}}, {{/}}, {{.}} and {{//}}");
+    println!("                          Consumes the rest of the command line, so it must come last");
+    println!("/H or --hidden            Includes hidden files and directories in the search");
+    println!("/I or --no-ignore         Disables .gitignore/.ignore filtering");
+    println!("/d or --max-depth <n>     Limits recursion to n directory levels deep");
+    println!("/t or --type <f|d|l>      Only matches files, directories, or symlinks");
+    println!("/e or --extension <ext>   Only matches paths ending in .<ext> (repeatable)");
+    println!("/C or --color <when>      Colorizes output using LS_COLORS: auto (default), always, or never");
+
+    println!("\nMade  by rip-super on Github (https://github.com/rip-super)");
+}
+
+// Walks the argument list, applying recognized flags to `options` and returning the search term.
+// `/x`/`--exec` consumes every argument after it as the command template, so it must come last.
+fn parse_args(args: &[String], file_name: &str, options: &mut Options) -> String {
+    if args.len() == 1 {
         eprintln!("Usage: {}[.exe] [args] <search_term>", file_name);
         eprintln!("enter '{} --help' to learn more", file_name);
         process::exit(1);
     }
 
-    let arg = &args[1];
-
-    if arg == "/h" || arg == "--help" {
-        println!("Usage: {}[.exe] [args] <search_term>", file_name);
-        println!("\nOPTIONAL ARGUMENTS\n");
-        println!("Note: Only one argument can be used at a time\n");
-        println!("/h or --help              Displays this help message");
-        println!("/b or --basename          Searches for files using their basename instead of their full path (case-insenitive)");
-        println!("/s or --case-sensitive    Searches for files using case-sensitive search");
-        println!("/c or --count             Only displays the number of matches and not the files matched");
-        println!("/l or --limit <number>    Limits the number of results displayed");
-        println!("/r or --regex <regexp>    Searches for files based on a regular expression");
-
-        println!("\nMade  by rip-super on Github (https://github.com/rip-super)");
-
-        process::exit(0)
-    } else if arg == "/b" || arg == "--basename" {
-        options.base_name = true;
-    } else if arg == "/s" || arg == "--case-sensitive" {
-        options.case_sens = true;
-    } else if arg == "/c" || arg == "--count" {
-        options.count = true;
-    } else if arg == "/l" || arg == "--limit" {
-        let limit = args[2].parse::<u32>();
-        match limit {
-            Ok(s) => options.limit = s,
-            Err(_) => {
-                eprintln!("Error: Expected value after limit flag to be a positive whole number");
-                eprintln!("enter '{} --help' to learn more", file_name);
-                process::exit(1)
+    let mut positional = Vec::new();
+    let mut i = 1;
+
+    while i < args.len() {
+        let arg = args[i].as_str();
+
+        match arg {
+            "/h" | "--help" => {
+                print_help(file_name);
+                process::exit(0);
             }
-        }
-    } else if arg == "/r" || arg == "--regex" {
-        let regex = Regex::new(&args[2]);
-        match regex {
-            Ok(s) => options.regex = Some(s),
-            Err(_) => {
-                eprintln!(
-                    "Error: Expected expression after regex flag to be a valid regular expression"
-                );
-                eprintln!("enter '{} --help' to learn more", file_name);
-                process::exit(1)
+            "/b" | "--basename" => {
+                options.base_name = true;
+                i += 1;
+            }
+            "/s" | "--case-sensitive" => {
+                options.case_sens = true;
+                i += 1;
+            }
+            "/c" | "--count" => {
+                options.count = true;
+                i += 1;
+            }
+            "/l" | "--limit" => {
+                match args.get(i + 1).and_then(|s| s.parse::<u32>().ok()) {
+                    Some(limit) => options.limit = limit,
+                    None => {
+                        eprintln!(
+                            "Error: Expected value after limit flag to be a positive whole number"
+                        );
+                        eprintln!("enter '{} --help' to learn more", file_name);
+                        process::exit(1);
+                    }
+                }
+                i += 2;
+            }
+            "/r" | "--regex" => match args.get(i + 1) {
+                Some(pattern) => match Regex::new(pattern) {
+                    Ok(regex) => {
+                        options.regex = Some(regex);
+                        i += 2;
+                    }
+                    Err(_) => {
+                        eprintln!("Error: Expected expression after regex flag to be a valid regular expression");
+                        eprintln!("enter '{} --help' to learn more", file_name);
+                        process::exit(1);
+                    }
+                },
+                None => {
+                    eprintln!("Error: Missing value after /r or --regex flag");
+                    eprintln!("enter '{} --help' to learn more", file_name);
+                    process::exit(1);
+                }
+            },
+            "/x" | "--exec" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: Missing command after /x or --exec flag");
+                    eprintln!("enter '{} --help' to learn more", file_name);
+                    process::exit(1);
+                }
+                options.exec = Some(args[i + 1..].to_vec());
+                i = args.len();
+            }
+            "/H" | "--hidden" => {
+                options.hidden = true;
+                i += 1;
+            }
+            "/I" | "--no-ignore" => {
+                options.no_ignore = true;
+                i += 1;
+            }
+            "/d" | "--max-depth" => {
+                match args.get(i + 1).and_then(|s| s.parse::<usize>().ok()) {
+                    Some(max_depth) => options.max_depth = Some(max_depth),
+                    None => {
+                        eprintln!(
+                            "Error: Expected value after max-depth flag to be a positive whole number"
+                        );
+                        eprintln!("enter '{} --help' to learn more", file_name);
+                        process::exit(1);
+                    }
+                }
+                i += 2;
+            }
+            "/t" | "--type" => {
+                match args.get(i + 1).map(|s| s.as_str()) {
+                    Some("f") => options.entry_type = Some(EntryType::File),
+                    Some("d") => options.entry_type = Some(EntryType::Dir),
+                    Some("l") => options.entry_type = Some(EntryType::Symlink),
+                    _ => {
+                        eprintln!("Error: Expected 'f', 'd', or 'l' after /t or --type flag");
+                        eprintln!("enter '{} --help' to learn more", file_name);
+                        process::exit(1);
+                    }
+                }
+                i += 2;
+            }
+            "/e" | "--extension" => match args.get(i + 1) {
+                Some(ext) => {
+                    options
+                        .extensions
+                        .push(ext.trim_start_matches('.').to_lowercase());
+                    i += 2;
+                }
+                None => {
+                    eprintln!("Error: Missing value after /e or --extension flag");
+                    eprintln!("enter '{} --help' to learn more", file_name);
+                    process::exit(1);
+                }
+            },
+            "/C" | "--color" => {
+                match args.get(i + 1).map(|s| s.as_str()) {
+                    Some("auto") => options.color = ColorMode::Auto,
+                    Some("always") => options.color = ColorMode::Always,
+                    Some("never") => options.color = ColorMode::Never,
+                    _ => {
+                        eprintln!("Error: Expected 'auto', 'always', or 'never' after /C or --color flag");
+                        eprintln!("enter '{} --help' to learn more", file_name);
+                        process::exit(1);
+                    }
+                }
+                i += 2;
+            }
+            _ => {
+                positional.push(args[i].clone());
+                i += 1;
             }
         }
     }
+
+    match positional.last() {
+        Some(search_term) => search_term.clone(),
+        None => {
+            eprintln!("Usage: {}[.exe] [args] <search_term>", file_name);
+            eprintln!("enter '{} --help' to learn more", file_name);
+            process::exit(1);
+        }
+    }
 }
 
 fn find_file(search_term: &str, options: &Options) {
-    let path = ".";
-    let mut files = Vec::new();
+    let mut builder = WalkBuilder::new(".");
+    builder.hidden(!options.hidden);
 
-    for entry in WalkDir::new(path) {
-        let entry = match entry {
-            Ok(e) => e,         // Successfully retrieve entry
-            Err(_) => continue, // Skip entries that cannot be accessed
-        };
+    if options.no_ignore {
+        builder
+            .ignore(false)
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .parents(false);
+    }
 
-        let metadata = match entry.metadata() {
-            Ok(meta) => meta,   // Successfully retrieve metadata
-            Err(_) => continue, // Skip files with inaccessible metadata
-        };
+    if options.max_depth.is_some() {
+        builder.max_depth(options.max_depth);
+    }
 
-        if metadata.is_file() && matches_search(entry.path(), search_term, options) {
-            files.push(entry.path().display().to_string());
-        }
+    let files = Mutex::new(Vec::new());
+
+    builder.build_parallel().run(|| {
+        Box::new(|entry| {
+            let entry = match entry {
+                Ok(e) => e,         // Successfully retrieve entry
+                Err(_) => return WalkState::Continue, // Skip entries that cannot be accessed
+            };
+
+            let type_matches = match options.entry_type.unwrap_or(EntryType::File) {
+                EntryType::File => entry.file_type().map(|ft| ft.is_file()).unwrap_or(false),
+                EntryType::Dir => entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false),
+                EntryType::Symlink => entry.file_type().map(|ft| ft.is_symlink()).unwrap_or(false),
+            };
+
+            if type_matches && matches_search(entry.path(), search_term, options) {
+                files.lock().unwrap().push(entry.path().display().to_string());
+            }
+
+            WalkState::Continue
+        })
+    });
+
+    let files = files.into_inner().unwrap();
+
+    if let Some(command) = &options.exec {
+        run_exec(&files, command, options.limit);
+        return;
     }
 
     if !options.count {
+        let color_enabled = match options.color {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => io::stdout().is_terminal(),
+        };
+        let ls_colors = color_enabled.then(LsColors::from_env);
+
         for (idx, file) in files.clone().into_iter().enumerate() {
             if idx as u32 == options.limit {
                 break;
             }
 
-            // Highlight the matched part
-            let highlighted = file.replace(
-                search_term,
-                &format!("\x1b[32m{}\x1b[0m", search_term), // Green ANSI escape codes
-            );
-            println!("{}", highlighted);
+            println!("{}", render_line(file.as_str(), search_term, options, ls_colors.as_ref()));
         }
     }
 
     println!("\n{} results found", files.len());
 }
 
+// Runs `command` once per matched path, substituting the fd-style placeholder tokens.
+fn run_exec(files: &[String], command: &[String], limit: u32) {
+    for (idx, file) in files.iter().enumerate() {
+        if idx as u32 == limit {
+            break;
+        }
+
+        let path = std::path::Path::new(file);
+        let full = file.clone();
+        let base_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| full.clone());
+        let no_ext = path
+            .with_extension("")
+            .to_string_lossy()
+            .into_owned();
+        let parent = path
+            .parent()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let substituted: Vec<String> = command
+            .iter()
+            .map(|token| {
+                token
+                    .replace("{//}", &parent)
+                    .replace("{/}", &base_name)
+                    .replace("{.}", &no_ext)
+                    .replace("{}", &full)
+            })
+            .collect();
+
+        let (program, rest) = match substituted.split_first() {
+            Some((program, rest)) => (program, rest),
+            None => continue,
+        };
+
+        match process::Command::new(program).args(rest).status() {
+            Ok(status) if !status.success() => {
+                eprintln!("Error: command exited with {} for '{}'", status, file);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error: failed to run '{}' for '{}': {}", program, file, e);
+            }
+        }
+    }
+}
+
+// Renders a matched path, applying the LS_COLORS type/extension color (if any) with a bold
+// overlay on the matched span, so both stay visible on top of each other.
+fn render_line(path: &str, search_term: &str, options: &Options, ls_colors: Option<&LsColors>) -> String {
+    let base_color = ls_colors.and_then(|colors| colors.color_for(Path::new(path)));
+
+    if base_color.is_none() && ls_colors.is_none() {
+        return path.to_string();
+    }
+
+    let mut out = String::new();
+    if let Some(code) = &base_color {
+        out.push_str(&format!("\x1b[{}m", code));
+    }
+
+    let mut last = 0;
+    for (start, end) in match_ranges(path, search_term, options) {
+        if start < last {
+            continue;
+        }
+        out.push_str(&path[last..start]);
+        out.push_str("\x1b[1m");
+        out.push_str(&path[start..end]);
+        out.push_str("\x1b[22m");
+        last = end;
+    }
+    out.push_str(&path[last..]);
+
+    if base_color.is_some() {
+        out.push_str("\x1b[0m");
+    }
+
+    out
+}
+
+// Finds the byte ranges in `path` that should be highlighted, honoring the active search mode.
+//
+// These ranges must stay valid byte offsets into `path` itself, since `render_line` slices the
+// original string with them: offsets taken from a `.to_lowercase()` copy can drift out of sync
+// whenever a character's lowercase form has a different UTF-8 byte length than the original
+// (e.g. 'ẞ' -> 'ß'), landing mid-codepoint and panicking on slice. The case-insensitive plain
+// search therefore only folds ASCII (see `find_all_ascii_ci`), which never changes byte length.
+fn match_ranges(path: &str, search_term: &str, options: &Options) -> Vec<(usize, usize)> {
+    if let Some(regex) = &options.regex {
+        let ci_regex = RegexBuilder::new(regex.as_str())
+            .case_insensitive(true)
+            .build()
+            .unwrap_or_else(|_| regex.clone());
+        ci_regex.find_iter(path).map(|m| (m.start(), m.end())).collect()
+    } else if options.case_sens {
+        find_all(path, search_term)
+    } else {
+        find_all_ascii_ci(path, search_term)
+    }
+}
+
+// Returns the non-overlapping byte ranges where `needle` occurs in `haystack`.
+fn find_all(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    while let Some(pos) = haystack[start..].find(needle) {
+        let match_start = start + pos;
+        let match_end = match_start + needle.len();
+        ranges.push((match_start, match_end));
+        start = match_end;
+    }
+
+    ranges
+}
+
+// Like `find_all`, but case-insensitive using ASCII-only case folding so the returned byte
+// ranges stay valid against the original `haystack` (ASCII lowercasing never changes byte length,
+// unlike full Unicode lowercasing).
+fn find_all_ascii_ci(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
+    find_all(&haystack.to_ascii_lowercase(), &needle.to_ascii_lowercase())
+}
+
 fn matches_search(path: &std::path::Path, search_term: &str, options: &Options) -> bool {
+    if !options.extensions.is_empty() {
+        let ext_matches = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                options
+                    .extensions
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(ext))
+            })
+            .unwrap_or(false);
+
+        if !ext_matches {
+            return false;
+        }
+    }
+
     if let Some(file_name) = path.file_name().and_then(|name| name.to_str()) {
         if options.base_name {
             // Case-insensitive search in base name
@@ -143,13 +541,14 @@ fn matches_search(path: &std::path::Path, search_term: &str, options: &Options)
         } else if options.case_sens {
             // Case-sensitive search in the full path
             path.display().to_string().contains(search_term)
-        } else if options.regex.is_some() {
-            // search with regex
-            options
-                .regex
-                .clone()
-                .unwrap()
-                .is_match(&path.display().to_string().to_lowercase())
+        } else if let Some(regex) = &options.regex {
+            // Case-insensitive regex search against the unmodified path, matching the
+            // highlighting path in `match_ranges` so selection and highlighting agree
+            let ci_regex = RegexBuilder::new(regex.as_str())
+                .case_insensitive(true)
+                .build()
+                .unwrap_or_else(|_| regex.clone());
+            ci_regex.is_match(&path.display().to_string())
         } else {
             // Case-insensitive search in full path
             path.display()
@@ -1,11 +1,16 @@
+use std::collections::VecDeque;
 use std::env;
 use std::fs;
-use std::io::{self, Read, Seek};
+use std::io::{self, BufRead, Read, Seek, Write};
 use std::process;
+use std::thread;
+use std::time::Duration;
 
 struct Options {
     num_lines: u32,
-    num_bytes: Option<u32>,
+    num_bytes: Option<u64>,
+    follow: bool,
+    from_start: bool, // true when the count was written as +N: start at N and print to end
 }
 
 impl Options {
@@ -13,6 +18,8 @@ impl Options {
         Options {
             num_lines: 10,
             num_bytes: None,
+            follow: false,
+            from_start: false,
         }
     }
 }
@@ -33,19 +40,36 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
-fn parse_size(size_str: &str) -> Option<u64> {
+// Returns (from_start, byte count). A leading '+' means "start at this byte and print to end",
+// matching coreutils' `-c +N` convention; a plain count keeps the tail-from-the-end behavior.
+fn parse_size(size_str: &str) -> Option<(bool, u64)> {
+    let (from_start, size_str) = match size_str.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, size_str),
+    };
+
     let chars = size_str.chars();
     let value: String = chars.clone().take_while(|c| c.is_ascii_digit()).collect();
     let unit: String = chars.skip_while(|c| c.is_ascii_digit()).collect();
 
     let value: u64 = value.parse().ok()?;
 
-    match unit.to_lowercase().as_str() {
-        "k" => Some(value * 1024),               // Kilobytes
-        "m" => Some(value * 1024 * 1024),        // Megabytes
-        "g" => Some(value * 1024 * 1024 * 1024), // Gigabytes
-        "" => Some(value),                       // Plain bytes
-        _ => None,                               // Invalid unit
+    let value = match unit.to_lowercase().as_str() {
+        "k" => value * 1024,               // Kilobytes
+        "m" => value * 1024 * 1024,        // Megabytes
+        "g" => value * 1024 * 1024 * 1024, // Gigabytes
+        "" => value,                       // Plain bytes
+        _ => return None,                  // Invalid unit
+    };
+
+    Some((from_start, value))
+}
+
+// Returns (from_start, line count), applying the same +N convention as `parse_size`.
+fn parse_line_count(count_str: &str) -> Option<(bool, u32)> {
+    match count_str.strip_prefix('+') {
+        Some(rest) => rest.parse().ok().map(|value| (true, value)),
+        None => count_str.parse().ok().map(|value| (false, value)),
     }
 }
 
@@ -63,18 +87,25 @@ fn check_args(args: &[String], file_name: &str, options: &mut Options) {
         println!("\nOPTIONAL ARGUMENTS\n");
         println!("Note: Only one argument can be used at a time\n");
         println!("/h or --help                  Displays this help message");
-        println!("/l or --num-lines <number>    Displays the first n lines of the file");
-        println!("/b or --num-bytes <size>      Displays the first n bytes of the file");
+        println!("/l or --num-lines <number>    Displays the last n lines of the file");
+        println!("                              A count written as '+N' (e.g. '+5') instead starts");
+        println!("                              at line N and prints to the end of the file");
+        println!("/b or --num-bytes <size>      Displays the last n bytes of the file");
         println!("                              (Also supports human-readable formats like:");
         println!("                              '2k' for 2 kilobytes,");
         println!("                              '3m' for 3 megabytes");
         println!("                              and '1g' for 1 gigabyte)");
+        println!("                              A size written as '+N' instead starts at byte N");
+        println!("                              and prints to the end of the file");
+        println!("/f or --follow                Keeps the file open and prints appended data as it is written");
         process::exit(0);
     } else if arg == "/l" || arg == "--num-lines" {
-        let num_lines = args[2].parse::<u32>();
-        match num_lines {
-            Ok(s) => options.num_lines = s,
-            Err(_) => {
+        match args.get(2).and_then(|s| parse_line_count(s)) {
+            Some((from_start, num_lines)) => {
+                options.num_lines = num_lines;
+                options.from_start = from_start;
+            }
+            None => {
                 eprintln!("Error: Expected value after /l or --num-lines flag to be a positive whole number");
                 eprintln!("Enter '{} --help' to learn more", file_name);
                 process::exit(1);
@@ -83,7 +114,10 @@ fn check_args(args: &[String], file_name: &str, options: &mut Options) {
     } else if arg == "/b" || arg == "--num-bytes" {
         if args.len() > 2 {
             match parse_size(&args[2]) {
-                Some(size) => options.num_bytes = Some(size as u32),
+                Some((from_start, size)) => {
+                    options.num_bytes = Some(size);
+                    options.from_start = from_start;
+                }
                 None => {
                     eprintln!(
                         "Error: Invalid size format '{}' after /b or --num-bytes flag",
@@ -98,6 +132,8 @@ fn check_args(args: &[String], file_name: &str, options: &mut Options) {
             eprintln!("Enter '{} --help' to learn more", file_name);
             process::exit(1);
         }
+    } else if arg == "/f" || arg == "--follow" {
+        options.follow = true;
     }
 }
 
@@ -110,10 +146,11 @@ fn display_files(args: &[String], file_name: &str, options: &mut Options) {
 
         if arg.starts_with('-') || arg.starts_with('/') {
             match arg.as_str() {
-                "--num-lines" | "/n" => {
+                "--num-lines" | "/l" | "/n" => {
                     if let Some(num_lines_str) = args.get(i + 1) {
-                        if let Ok(num_lines) = num_lines_str.parse::<u32>() {
+                        if let Some((from_start, num_lines)) = parse_line_count(num_lines_str) {
                             options.num_lines = num_lines;
+                            options.from_start = from_start;
                         } else {
                             eprintln!("Error: Invalid number of lines '{}'", num_lines_str);
                             eprintln!("Enter '{} --help' to learn more", file_name);
@@ -130,7 +167,10 @@ fn display_files(args: &[String], file_name: &str, options: &mut Options) {
                 "--num-bytes" | "/b" => {
                     if let Some(num_bytes_str) = args.get(i + 1) {
                         match parse_size(num_bytes_str) {
-                            Some(size) => options.num_bytes = Some(size as u32),
+                            Some((from_start, size)) => {
+                                options.num_bytes = Some(size);
+                                options.from_start = from_start;
+                            }
                             None => {
                                 eprintln!("Error: Invalid size format '{}'", num_bytes_str);
                                 eprintln!("Enter '{} --help' to learn more", file_name);
@@ -145,6 +185,11 @@ fn display_files(args: &[String], file_name: &str, options: &mut Options) {
                     i += 2;
                     continue;
                 }
+                "--follow" | "/f" => {
+                    options.follow = true;
+                    i += 1;
+                    continue;
+                }
                 _ => {
                     eprintln!("Error: Unknown argument '{}'", args[i]);
                     process::exit(1);
@@ -157,57 +202,122 @@ fn display_files(args: &[String], file_name: &str, options: &mut Options) {
             file_paths.push(arg.clone());
             i += 1;
         }
+    }
 
-        for file_path in &file_paths {
-            match fs::File::open(file_path) {
-                Ok(file) => {
-                    let file_len = file.metadata().unwrap().len();
-                    let mut reader = io::BufReader::new(file);
+    for file_path in &file_paths {
+        match fs::File::open(file_path) {
+            Ok(file) => {
+                let file_len = file.metadata().unwrap().len();
+                let mut reader = io::BufReader::new(file);
 
-                    let string = format!("==> \x1b[32m{}\x1b[0m <==", file_path);
-                    println!("{}", string);
-                    println!("{}", "-".repeat(string.len()));
+                let string = format!("==> \x1b[32m{}\x1b[0m <==", file_path);
+                println!("{}", string);
+                println!("{}", "-".repeat(string.len()));
 
-                    if let Some(num_bytes) = options.num_bytes {
-                        let start_pos = if file_len > num_bytes as u64 {
-                            file_len - num_bytes as u64
-                        } else {
-                            0
-                        };
-
-                        // Seek to the calculated position
-                        reader.seek(io::SeekFrom::Start(start_pos)).unwrap();
+                if let Some(num_bytes) = options.num_bytes {
+                    // +N means "start at byte N (1-indexed) and print to the end";
+                    // a plain count keeps printing the last N bytes.
+                    let start_pos = if options.from_start {
+                        num_bytes.saturating_sub(1)
+                    } else {
+                        file_len.saturating_sub(num_bytes)
+                    };
 
-                        // Create a buffer and read the bytes
-                        let mut buffer = vec![0; num_bytes as usize];
-                        let bytes_read = reader.read(&mut buffer).unwrap_or(0);
+                    // Seek to the calculated position and stream the rest straight to
+                    // stdout instead of buffering the whole tail in memory
+                    reader.seek(io::SeekFrom::Start(start_pos)).unwrap();
+                    io::copy(&mut reader, &mut io::stdout()).unwrap();
+                } else if options.from_start {
+                    // +N means "start at line N (1-indexed) and print to the end"; stream
+                    // line-by-line since nothing before the start line needs to be kept
+                    let start_line = options.num_lines.max(1) as usize;
 
-                        // Print the result
-                        print!("{}", String::from_utf8_lossy(&buffer[..bytes_read]));
-                    } else {
-                        let mut contents = String::new();
-                        reader.read_to_string(&mut contents).unwrap();
-                        let contents: Vec<&str> = contents.split('\n').collect();
-
-                        // Display the **last** `options.num_lines` lines
-                        let num_lines = options.num_lines as usize;
-                        let start_index = if contents.len() > num_lines {
-                            contents.len() - num_lines
-                        } else {
-                            0
-                        };
+                    for (idx, line) in reader.lines().enumerate() {
+                        if idx + 1 >= start_line {
+                            println!("{}", line.unwrap_or_default());
+                        }
+                    }
+                } else {
+                    // Keep only the last `num_lines` lines in memory as we scan,
+                    // so multi-gigabyte logs never get fully loaded at once
+                    let num_lines = options.num_lines as usize;
+                    let mut ring: VecDeque<String> = VecDeque::with_capacity(num_lines);
 
-                        for line in &contents[start_index..] {
-                            println!("{}", line);
+                    for line in reader.lines() {
+                        ring.push_back(line.unwrap_or_default());
+                        if ring.len() > num_lines {
+                            ring.pop_front();
                         }
                     }
 
-                    println!();
+                    for line in &ring {
+                        println!("{}", line);
+                    }
                 }
-                Err(e) => {
-                    eprintln!("Error reading file '{}': {}", &file_path, e);
-                    process::exit(1);
+
+                println!();
+            }
+            Err(e) => {
+                eprintln!("Error reading file '{}': {}", &file_path, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    if options.follow {
+        follow_files(&file_paths);
+    }
+}
+
+/// Keeps polling `file_paths` for appended data once the initial tail has been printed,
+/// re-printing the `==> name <==` header only when the active file changes.
+fn follow_files(file_paths: &[String]) -> ! {
+    let mut offsets: Vec<u64> = file_paths
+        .iter()
+        .map(|path| fs::metadata(path).map(|m| m.len()).unwrap_or(0))
+        .collect();
+    // Seed with the file whose header the initial tail printed last, so a single followed
+    // file (or re-appending to whichever file was already active) doesn't re-announce itself.
+    let mut last_active: Option<usize> = file_paths.len().checked_sub(1);
+
+    loop {
+        thread::sleep(Duration::from_millis(200));
+
+        for (idx, file_path) in file_paths.iter().enumerate() {
+            let file_len = match fs::metadata(file_path) {
+                Ok(meta) => meta.len(),
+                Err(_) => continue,
+            };
+
+            if file_len < offsets[idx] {
+                // File was truncated or rotated; start over from the beginning.
+                offsets[idx] = 0;
+            }
+
+            if file_len == offsets[idx] {
+                continue;
+            }
+
+            if let Ok(mut file) = fs::File::open(file_path) {
+                if file.seek(io::SeekFrom::Start(offsets[idx])).is_err() {
+                    continue;
+                }
+
+                let mut buffer = Vec::new();
+                if file.read_to_end(&mut buffer).is_err() {
+                    continue;
                 }
+
+                if last_active != Some(idx) {
+                    let string = format!("==> \x1b[32m{}\x1b[0m <==", file_path);
+                    println!("{}", string);
+                    println!("{}", "-".repeat(string.len()));
+                    last_active = Some(idx);
+                }
+
+                print!("{}", String::from_utf8_lossy(&buffer));
+                io::stdout().flush().ok();
+                offsets[idx] = file_len;
             }
         }
     }
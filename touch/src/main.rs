@@ -7,10 +7,13 @@ use std::process;
 use std::time::SystemTime;
 
 struct Options {
-    no_create: bool, // Prevent file creation if it doesn't exist
-    directory: bool, // Create directories instead of files
-    acc_time: bool,  // Update only access time
-    mod_time: bool,  // Update only modification time
+    no_create: bool,              // Prevent file creation if it doesn't exist
+    directory: bool,              // Create directories instead of files
+    acc_time: bool,               // Update only access time
+    mod_time: bool,               // Update only modification time
+    reference: Option<String>,    // Copy timestamps from this file instead of using now
+    timestamp: Option<String>,    // Explicit [[CC]YY]MMDDhhmm[.ss] stamp
+    date: Option<String>,         // Explicit ISO-8601-ish date string
 }
 
 impl Options {
@@ -20,6 +23,9 @@ impl Options {
             directory: false,
             acc_time: false,
             mod_time: false,
+            reference: None,
+            timestamp: None,
+            date: None,
         }
     }
 }
@@ -42,19 +48,38 @@ fn main() -> io::Result<()> {
         "/m",
         "--modification-time",
     ]; // Recognized arguments
+    let value_arguments = [
+        "/r",
+        "--reference",
+        "/t",
+        "--timestamp",
+        "/D",
+        "--date",
+    ]; // Recognized arguments that also consume the following value
     let mut options = Options::new(); // Initialize options
 
     check_args(&args, &file_name, &mut options); // Parse and set options based on args passed in
 
-    for arg in args.iter() {
-        if (arg == &file_name.to_string() || arg == &file_name_exe.to_string())
-            || (arguments.contains(&arg.as_str()))
-        // assume user does not want to name a file/folder one of the arguments
-        {
-            continue; // Skip the executable name and recognized arguments
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+
+        if arg == &file_name.to_string() || arg == &file_name_exe.to_string() {
+            // Skip the executable name
+            i += 1;
+            continue;
+        } else if arguments.contains(&arg.as_str()) {
+            // Skip recognized boolean flags
+            i += 1;
+            continue;
+        } else if value_arguments.contains(&arg.as_str()) {
+            // Skip recognized flags and the value that follows them
+            i += 2;
+            continue;
         }
 
         make_file(arg, &options); // Process file creation or updates
+        i += 1;
     }
 
     Ok(())
@@ -80,9 +105,12 @@ fn check_args(args: &[String], file_name: &str, options: &mut Options) {
         println!("\n/d or --directory           Creates directories instead of files\n                            Can also create nested folders:\n                            {} --directory this/is/a/nested/folder\n", file_name);
         println!("/a or --access-time         Only updates the accessed time of the file if the file already exists,\n                            otherwise it creates the file like normal\n");
         println!("\n/m or --modification-time   Only updates the modified time of the file if the file already exists,\n                            otherwise it creates the file like normal\n");
+        println!("/r or --reference <file>    Uses <file>'s accessed and modified times instead of now\n");
+        println!("/t or --timestamp <stamp>   Uses an explicit [[CC]YY]MMDDhhmm[.ss] timestamp instead of now\n");
+        println!("/D or --date <string>       Uses an explicit ISO-8601-ish date (e.g. '2024-01-31' or '2024-01-31T10:30:00') instead of now\n                            Capital /D since lowercase /d is already --directory\n");
 
         println!("\nMade  by rip-super on Github (https://github.com/rip-super)");
-        
+
         process::exit(0);
     } else if arg == "/c" || arg == "--no-create" {
         options.no_create = true;
@@ -92,6 +120,35 @@ fn check_args(args: &[String], file_name: &str, options: &mut Options) {
         options.acc_time = true;
     } else if arg == "/m" || arg == "--modification-time" {
         options.mod_time = true;
+    } else if arg == "/r" || arg == "--reference" {
+        match args.get(2) {
+            Some(reference) => options.reference = Some(reference.clone()),
+            None => {
+                eprintln!("Error: Expected a file path after /r or --reference flag");
+                eprintln!("enter '{} --help' to learn more", file_name);
+                process::exit(1);
+            }
+        }
+    } else if arg == "/t" || arg == "--timestamp" {
+        match args.get(2) {
+            Some(stamp) => options.timestamp = Some(stamp.clone()),
+            None => {
+                eprintln!("Error: Expected a [[CC]YY]MMDDhhmm[.ss] stamp after /t or --timestamp flag");
+                eprintln!("enter '{} --help' to learn more", file_name);
+                process::exit(1);
+            }
+        }
+    } else if arg == "/D" || arg == "--date" {
+        // Capital /D: lowercase /d is already taken by --directory
+
+        match args.get(2) {
+            Some(date_str) => options.date = Some(date_str.clone()),
+            None => {
+                eprintln!("Error: Expected a date string after /D or --date flag");
+                eprintln!("enter '{} --help' to learn more", file_name);
+                process::exit(1);
+            }
+        }
     }
 }
 
@@ -115,18 +172,22 @@ fn make_file(file_name: &String, options: &Options) {
             }
         }
     } else if options.acc_time {
-        create_file(file_name, "a"); // Update access time only
+        create_file(file_name, "a", options); // Update access time only
     } else if options.mod_time {
-        create_file(file_name, "m"); // Update modification time only
+        create_file(file_name, "m", options); // Update modification time only
     } else {
-        create_file(file_name, "none"); // Create or update both timestamps
+        create_file(file_name, "none", options); // Create or update both timestamps
     }
 }
 
 // Handle file creation and timestamp updates
-fn create_file(file_name: &String, type_: &str) {
+fn create_file(file_name: &String, type_: &str, options: &Options) {
     match File::create_new(file_name) {
-        Ok(_) => {}
+        Ok(_) => {
+            if let Some((atime, mtime)) = resolve_target_time(options) {
+                apply_times(file_name, type_, atime, mtime);
+            }
+        }
         Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
             eprintln!(
                 "Error: Unable to create the file '{}'.\nPossible reasons:\n - Insufficient permissions.\n - Invalid file name.",
@@ -135,17 +196,12 @@ fn create_file(file_name: &String, type_: &str) {
             process::exit(1);
         }
         Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
-            let now = SystemTime::now();
-            let now_filetime = FileTime::from_system_time(now);
+            let (atime, mtime) = resolve_target_time(options).unwrap_or_else(|| {
+                let now = FileTime::from_system_time(SystemTime::now());
+                (now, now)
+            });
 
-            if type_ == "a" {
-                filetime::set_file_atime(file_name, now_filetime).unwrap(); // Update access time
-            } else if type_ == "m" {
-                filetime::set_file_mtime(file_name, now_filetime).unwrap(); // Update modification time
-            } else {
-                // Update both
-                filetime::set_file_times(file_name, now_filetime, now_filetime).unwrap();
-            }
+            apply_times(file_name, type_, atime, mtime);
         }
         Err(e) => {
             eprintln!("An unexpected error occurred: {}", e);
@@ -153,3 +209,193 @@ fn create_file(file_name: &String, type_: &str) {
         }
     }
 }
+
+// Apply the resolved access/modification times, honoring the -a/-m selectors
+fn apply_times(file_name: &str, type_: &str, atime: FileTime, mtime: FileTime) {
+    if type_ == "a" {
+        filetime::set_file_atime(file_name, atime).unwrap(); // Update access time
+    } else if type_ == "m" {
+        filetime::set_file_mtime(file_name, mtime).unwrap(); // Update modification time
+    } else {
+        // Update both
+        filetime::set_file_times(file_name, atime, mtime).unwrap();
+    }
+}
+
+// Figure out which (atime, mtime) pair to apply based on -r/-t/-D, or None to mean "use now"
+fn resolve_target_time(options: &Options) -> Option<(FileTime, FileTime)> {
+    if let Some(reference) = &options.reference {
+        match fs::metadata(reference) {
+            Ok(metadata) => Some((
+                FileTime::from_last_access_time(&metadata),
+                FileTime::from_last_modification_time(&metadata),
+            )),
+            Err(e) => {
+                eprintln!(
+                    "Error: Unable to read reference file '{}': {}",
+                    reference, e
+                );
+                process::exit(1);
+            }
+        }
+    } else if let Some(stamp) = &options.timestamp {
+        match parse_timestamp(stamp) {
+            Some(filetime) => Some((filetime, filetime)),
+            None => {
+                eprintln!(
+                    "Error: Invalid timestamp '{}', expected format [[CC]YY]MMDDhhmm[.ss]",
+                    stamp
+                );
+                process::exit(1);
+            }
+        }
+    } else if let Some(date_str) = &options.date {
+        match parse_date(date_str) {
+            Some(filetime) => Some((filetime, filetime)),
+            None => {
+                eprintln!(
+                    "Error: Invalid date '{}', expected an ISO-8601-ish date like '2024-01-31' or '2024-01-31T10:30:00'",
+                    date_str
+                );
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    }
+}
+
+// Parse a coreutils-style [[CC]YY]MMDDhhmm[.ss] stamp into a FileTime
+fn parse_timestamp(stamp: &str) -> Option<FileTime> {
+    let (main_part, seconds) = match stamp.split_once('.') {
+        Some((main_part, sec_part)) => {
+            if sec_part.len() != 2 || !sec_part.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            (main_part, sec_part.parse::<u32>().ok()?)
+        }
+        None => (stamp, 0),
+    };
+
+    if !main_part.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let (year, month_day_time) = match main_part.len() {
+        12 => (main_part[0..4].parse::<i64>().ok()?, &main_part[4..]),
+        10 => {
+            let yy: i64 = main_part[0..2].parse().ok()?;
+            (if yy < 69 { 2000 + yy } else { 1900 + yy }, &main_part[2..])
+        }
+        8 => (civil_from_days(days_since_epoch_now()).0, main_part),
+        _ => return None,
+    };
+
+    if month_day_time.len() != 8 {
+        return None;
+    }
+
+    let month: u32 = month_day_time[0..2].parse().ok()?;
+    let day: u32 = month_day_time[2..4].parse().ok()?;
+    let hour: u32 = month_day_time[4..6].parse().ok()?;
+    let minute: u32 = month_day_time[6..8].parse().ok()?;
+
+    build_filetime(year, month, day, hour, minute, seconds)
+}
+
+// Parse a minimal ISO-8601-ish date, e.g. '2024-01-31' or '2024-01-31T10:30:00'
+fn parse_date(date_str: &str) -> Option<FileTime> {
+    let (date_part, time_part) = match date_str.split_once(['T', ' ']) {
+        Some((d, t)) => (d, Some(t)),
+        None => (date_str, None),
+    };
+
+    let mut date_fields = date_part.split('-');
+    let year: i64 = date_fields.next()?.parse().ok()?;
+    let month: u32 = date_fields.next()?.parse().ok()?;
+    let day: u32 = date_fields.next()?.parse().ok()?;
+    if date_fields.next().is_some() {
+        return None;
+    }
+
+    let (hour, minute, second) = match time_part {
+        Some(time) => {
+            let mut time_fields = time.split(':');
+            let hour: u32 = time_fields.next()?.parse().ok()?;
+            let minute: u32 = time_fields.next().unwrap_or("0").parse().ok()?;
+            let second: u32 = time_fields.next().unwrap_or("0").parse().ok()?;
+            (hour, minute, second)
+        }
+        None => (0, 0, 0),
+    };
+
+    build_filetime(year, month, day, hour, minute, second)
+}
+
+fn build_filetime(year: i64, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<FileTime> {
+    if !(1..=12).contains(&month) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    if !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86400 + hour as i64 * 3600 + minute as i64 * 60 + second as i64;
+
+    Some(FileTime::from_unix_time(seconds, 0))
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+// Number of days in (year, month); used to validate the day component of an explicit date.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 0,
+    }
+}
+
+fn days_since_epoch_now() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+        / 86400
+}
+
+// Howard Hinnant's days-from-civil algorithm: days since 1970-01-01 for a given (y, m, d)
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400; // [0, 399]
+    let mp = (m as i64 + 9) % 12; // [0, 11]
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+    era * 146097 + doe - 719468
+}
+
+// Inverse of days_from_civil: (y, m, d) for a given day count since 1970-01-01
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}